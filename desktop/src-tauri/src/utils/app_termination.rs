@@ -1,74 +1,231 @@
 use objc;
-use std::sync::Once;
-use tauri::AppHandle;
+use serde::Serialize;
+use std::sync::{Once, OnceLock};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
 use tokio::runtime::Runtime;
 
 // Ensure this is initialized only once
 static INIT: Once = Once::new();
-// Store our observer to prevent it from being dropped
 
-static mut OBSERVER: Option<*mut std::ffi::c_void> = None;
+// Ensure the power observer is initialized only once
+static POWER_INIT: Once = Once::new();
 
-// Set up applicationWillTerminate listener
-pub fn setup_termination_handler(app_handle: AppHandle) {
+// Ensure the Apple Event URL handler is initialized only once
+static URL_INIT: Once = Once::new();
+
+// The objc observer instances below are only ever written once, from inside
+// their respective `Once::call_once`, and read never — we just need to keep
+// them alive for the lifetime of the process. `*mut c_void` isn't `Send`/
+// `Sync` by default, so wrap it rather than reach for `static mut`, which
+// the compiler now refuses to let us take a reference to safely.
+struct ObjcInstance(*mut std::ffi::c_void);
+unsafe impl Send for ObjcInstance {}
+unsafe impl Sync for ObjcInstance {}
+
+static OBSERVER: OnceLock<ObjcInstance> = OnceLock::new();
+static POWER_OBSERVER: OnceLock<ObjcInstance> = OnceLock::new();
+static URL_OBSERVER: OnceLock<ObjcInstance> = OnceLock::new();
+
+// `AppHandle` is registered once at startup and then only ever read from the
+// various Objective-C callbacks, so a `OnceLock` holder is a better fit than
+// boxing and leaking a pointer per observer. `AppHandle` is already `Send +
+// Sync` in Tauri (it's routinely moved into `tokio::spawn`/async commands),
+// so no wrapper/unsafe impl is needed here.
+static APP_HANDLE: OnceLock<AppHandle> = OnceLock::new();
+
+fn store_app_handle(app_handle: AppHandle) {
+    // All three `register_*`/`setup_*` entry points are called with the same
+    // handle at startup, so whichever runs first wins and the rest are no-ops.
+    let _ = APP_HANDLE.set(app_handle);
+}
+
+fn app_handle() -> &'static AppHandle {
+    APP_HANDLE
+        .get()
+        .expect("app handle accessed before any observer was registered")
+}
+
+// A single tokio runtime shared by every Objective-C callback that needs to
+// block on async work, instead of spinning one up per notification.
+static RUNTIME: OnceLock<Runtime> = OnceLock::new();
+
+fn runtime() -> &'static Runtime {
+    RUNTIME.get_or_init(|| Runtime::new().expect("failed to create shared termination runtime"))
+}
+
+// How long we give `cleanup_all_processes` to finish during
+// `applicationWillTerminate:` before abandoning it. macOS only grants a short
+// window here before force-killing the app, so this must stay well under
+// that. Overridable for slower machines/CI via `OPENBB_SHUTDOWN_TIMEOUT_MS`.
+const DEFAULT_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(3);
+
+fn shutdown_timeout() -> Duration {
+    std::env::var("OPENBB_SHUTDOWN_TIMEOUT_MS")
+        .ok()
+        .and_then(|value| value.parse::<u64>().ok())
+        .map(Duration::from_millis)
+        .unwrap_or(DEFAULT_SHUTDOWN_TIMEOUT)
+}
+
+// Tauri event emitted to the frontend for every NSApplication lifecycle
+// notification we observe.
+const LIFECYCLE_EVENT: &str = "app-lifecycle";
+
+/// Typed mirror of the NSApplication lifecycle notifications we observe,
+/// forwarded to the frontend so it can react to things like focus/blur
+/// without polling the window state itself.
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum LifecycleEvent {
+    DidFinishLaunching,
+    DidBecomeActive,
+    WillResignActive,
+    DidHide,
+    WillTerminate,
+}
+
+// Tauri event emitted to the frontend for an `openbb://` deep link.
+const DEEP_LINK_EVENT: &str = "deep-link";
+
+// Whether NSApplication has posted `applicationDidFinishLaunching:`, which is
+// taken as the point from which the frontend can receive events. Deep links
+// that arrive before then are queued in `PENDING_DEEP_LINKS` and flushed once
+// we flip this.
+static FRONTEND_READY: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+static PENDING_DEEP_LINKS: OnceLock<std::sync::Mutex<Vec<String>>> = OnceLock::new();
+
+fn pending_deep_links() -> &'static std::sync::Mutex<Vec<String>> {
+    PENDING_DEEP_LINKS.get_or_init(|| std::sync::Mutex::new(Vec::new()))
+}
+
+// Emit a deep link to the frontend if it's ready, otherwise queue it for
+// `flush_pending_deep_links` to pick up once `DidFinishLaunching` fires.
+fn emit_or_queue_deep_link(app_handle: &AppHandle, url: String) {
+    if FRONTEND_READY.load(std::sync::atomic::Ordering::SeqCst) {
+        if let Err(err) = app_handle.emit(DEEP_LINK_EVENT, url) {
+            log::warn!("failed to emit {} event: {}", DEEP_LINK_EVENT, err);
+        }
+    } else {
+        log::debug!("frontend not ready yet, queuing deep link");
+        pending_deep_links().lock().unwrap().push(url);
+    }
+}
+
+fn flush_pending_deep_links(app_handle: &AppHandle) {
+    for url in pending_deep_links().lock().unwrap().drain(..) {
+        if let Err(err) = app_handle.emit(DEEP_LINK_EVENT, url) {
+            log::warn!("failed to emit queued {} event: {}", DEEP_LINK_EVENT, err);
+        }
+    }
+}
+
+// Emit a `LifecycleEvent` to the frontend, and additionally run the backend
+// cleanup when it's the terminate variant.
+fn dispatch_lifecycle_event(app_handle: &AppHandle, event: LifecycleEvent) {
+    log::debug!("{:?} received, emitting {} event...", event, LIFECYCLE_EVENT);
+
+    if let Err(err) = app_handle.emit(LIFECYCLE_EVENT, event) {
+        log::warn!("failed to emit {} event: {}", LIFECYCLE_EVENT, err);
+    }
+
+    if matches!(event, LifecycleEvent::DidFinishLaunching) {
+        FRONTEND_READY.store(true, std::sync::atomic::Ordering::SeqCst);
+        flush_pending_deep_links(app_handle);
+    }
+
+    if matches!(event, LifecycleEvent::WillTerminate) {
+        crate::utils::notifications::notify_shutdown();
+
+        let budget = shutdown_timeout();
+        runtime().block_on(async {
+            let cleanup = crate::cleanup_all_processes(app_handle.clone());
+            if tokio::time::timeout(budget, cleanup).await.is_err() {
+                log::warn!(
+                    "backend cleanup did not finish within {budget:?}; abandoning teardown so macOS can proceed"
+                );
+            }
+        });
+
+        // Don't call exit() as it may interfere with macOS shutdown
+    }
+}
+
+// Register a single NSObject subclass as an observer for the full set of
+// NSApplication lifecycle notifications we care about, forwarding each as a
+// typed `LifecycleEvent` to the frontend.
+pub fn register_lifecycle_observers(app_handle: AppHandle) {
     INIT.call_once(|| {
+        store_app_handle(app_handle);
+
         unsafe {
             // Import objc macros
             use objc::runtime::{Class, Object, Sel};
             use objc::{msg_send, sel, sel_impl};
-            use std::ffi::c_void;
-
-            // Create a static reference to the app handle for use in the callback
-            let app_ptr = Box::into_raw(Box::new(app_handle)) as *mut c_void;
 
             // Define our Objective-C class
             let superclass = Class::get("NSObject").unwrap();
             let mut decl =
-                objc::declare::ClassDecl::new("OBBAppTerminationObserver", superclass).unwrap();
-
-            // Add instance variable to store AppHandle
-            decl.add_ivar::<*mut c_void>("appHandlePtr");
+                objc::declare::ClassDecl::new("OBBAppLifecycleObserver", superclass).unwrap();
 
-            // Implement the handler for applicationWillTerminate
-            extern "C" fn will_terminate(this: &Object, _cmd: Sel, _notification: *mut Object) {
-                log::debug!("applicationWillTerminate received, running cleanup...");
-
-                unsafe {
-                    // Retrieve the app handle pointer
-                    let app_ptr: *mut c_void = *this.get_ivar("appHandlePtr");
-                    let app_handle = &*(app_ptr as *const AppHandle);
-
-                    // Create a runtime and run the cleanup handler
-                    if let Ok(rt) = Runtime::new() {
-                        rt.block_on(async {
-                            crate::cleanup_all_processes(app_handle.clone()).await;
-                        });
-                    }
-                }
-
-                // Don't call exit() as it may interfere with macOS shutdown
+            extern "C" fn did_finish_launching(_this: &Object, _cmd: Sel, _notification: *mut Object) {
+                dispatch_lifecycle_event(app_handle(), LifecycleEvent::DidFinishLaunching)
+            }
+            extern "C" fn did_become_active(_this: &Object, _cmd: Sel, _notification: *mut Object) {
+                dispatch_lifecycle_event(app_handle(), LifecycleEvent::DidBecomeActive)
+            }
+            extern "C" fn will_resign_active(_this: &Object, _cmd: Sel, _notification: *mut Object) {
+                dispatch_lifecycle_event(app_handle(), LifecycleEvent::WillResignActive)
+            }
+            extern "C" fn did_hide(_this: &Object, _cmd: Sel, _notification: *mut Object) {
+                dispatch_lifecycle_event(app_handle(), LifecycleEvent::DidHide)
+            }
+            extern "C" fn will_terminate(_this: &Object, _cmd: Sel, _notification: *mut Object) {
+                dispatch_lifecycle_event(app_handle(), LifecycleEvent::WillTerminate)
             }
 
-            // Add the method to our class
+            // Add the methods to our class
+            #[allow(unexpected_cfgs)]
+            let sel_did_finish_launching = sel!(applicationDidFinishLaunching:);
+            #[allow(unexpected_cfgs)]
+            let sel_did_become_active = sel!(applicationDidBecomeActive:);
+            #[allow(unexpected_cfgs)]
+            let sel_will_resign_active = sel!(applicationWillResignActive:);
+            #[allow(unexpected_cfgs)]
+            let sel_did_hide = sel!(applicationDidHide:);
             #[allow(unexpected_cfgs)]
             let sel_app_will_terminate = sel!(applicationWillTerminate:);
+
+            decl.add_method(
+                sel_did_finish_launching,
+                did_finish_launching as extern "C" fn(&Object, Sel, *mut Object),
+            );
+            decl.add_method(
+                sel_did_become_active,
+                did_become_active as extern "C" fn(&Object, Sel, *mut Object),
+            );
+            decl.add_method(
+                sel_will_resign_active,
+                will_resign_active as extern "C" fn(&Object, Sel, *mut Object),
+            );
+            decl.add_method(
+                sel_did_hide,
+                did_hide as extern "C" fn(&Object, Sel, *mut Object),
+            );
             decl.add_method(
                 sel_app_will_terminate,
                 will_terminate as extern "C" fn(&Object, Sel, *mut Object),
             );
 
             // Register the class
-            let termination_observer_class = decl.register();
+            let lifecycle_observer_class = decl.register();
 
             // Create an instance
             #[allow(unexpected_cfgs)]
-            let observer: *mut Object = msg_send![termination_observer_class, new];
-
-            // Store the app handle pointer in the instance variable
-            (*observer).set_ivar("appHandlePtr", app_ptr);
+            let observer: *mut Object = msg_send![lifecycle_observer_class, new];
 
             // Store the observer in our static to prevent it from being dropped
-            OBSERVER = Some(observer as *mut c_void);
+            let _ = OBSERVER.set(ObjcInstance(observer as *mut std::ffi::c_void));
 
             // Get the notification center
             let notification_center_class = Class::get("NSNotificationCenter").unwrap();
@@ -82,24 +239,253 @@ pub fn setup_termination_handler(app_handle: AppHandle) {
             #[allow(unexpected_cfgs)]
             let app: *mut Object = msg_send![app_class, sharedApplication];
 
-            // Create NSString for notification name
+            // Register the observer for each notification name/selector pair
+            let notifications: &[(&str, Sel)] = &[
+                ("NSApplicationDidFinishLaunchingNotification", sel_did_finish_launching),
+                ("NSApplicationDidBecomeActiveNotification", sel_did_become_active),
+                ("NSApplicationWillResignActiveNotification", sel_will_resign_active),
+                ("NSApplicationDidHideNotification", sel_did_hide),
+                ("NSApplicationWillTerminateNotification", sel_app_will_terminate),
+            ];
+
+            for (name, selector) in notifications {
+                #[allow(unexpected_cfgs)]
+                let notification_name: *mut Object = {
+                    let nsstring_class = Class::get("NSString").unwrap();
+                    let cstr = std::ffi::CString::new(*name).unwrap();
+                    msg_send![nsstring_class, stringWithUTF8String: cstr.as_ptr()]
+                };
+                #[allow(unexpected_cfgs)]
+                let _: () = msg_send![
+                    notification_center,
+                    addObserver:observer
+                    selector:*selector
+                    name:notification_name
+                    object:app
+                ];
+            }
+            log::debug!("NSApplication lifecycle observers registered successfully");
+        }
+    });
+}
+
+// Set up NSWorkspace sleep/wake listeners so the backend is suspended across
+// a lid-close and resumed on wake instead of leaving long-lived connections
+// hanging for the OS to eventually tear down.
+pub fn setup_power_management_handler(app_handle: AppHandle) {
+    POWER_INIT.call_once(|| {
+        store_app_handle(app_handle);
+
+        unsafe {
+            // Import objc macros
+            use objc::runtime::{Class, Object, Sel};
+            use objc::{msg_send, sel, sel_impl};
+
+            // Define our Objective-C class
+            let superclass = Class::get("NSObject").unwrap();
+            let mut decl =
+                objc::declare::ClassDecl::new("OBBAppPowerObserver", superclass).unwrap();
+
+            // Implement the handler for NSWorkspaceWillSleepNotification
+            extern "C" fn will_sleep(_this: &Object, _cmd: Sel, _notification: *mut Object) {
+                log::debug!("NSWorkspaceWillSleepNotification received, suspending backend...");
+                runtime().block_on(async {
+                    crate::utils::process_control::suspend_all_processes(app_handle().clone())
+                        .await;
+                });
+            }
+
+            // Implement the handler for NSWorkspaceDidWakeNotification
+            extern "C" fn did_wake(_this: &Object, _cmd: Sel, _notification: *mut Object) {
+                log::debug!("NSWorkspaceDidWakeNotification received, resuming backend...");
+                runtime().block_on(async {
+                    crate::utils::process_control::resume_all_processes(app_handle().clone())
+                        .await;
+                });
+            }
+
+            // Add the methods to our class
+            #[allow(unexpected_cfgs)]
+            let sel_will_sleep = sel!(willSleep:);
+            #[allow(unexpected_cfgs)]
+            let sel_did_wake = sel!(didWake:);
+            decl.add_method(
+                sel_will_sleep,
+                will_sleep as extern "C" fn(&Object, Sel, *mut Object),
+            );
+            decl.add_method(
+                sel_did_wake,
+                did_wake as extern "C" fn(&Object, Sel, *mut Object),
+            );
+
+            // Register the class
+            let power_observer_class = decl.register();
+
+            // Create an instance
+            #[allow(unexpected_cfgs)]
+            let observer: *mut Object = msg_send![power_observer_class, new];
+
+            // Store the observer in our static to prevent it from being dropped
+            let _ = POWER_OBSERVER.set(ObjcInstance(observer as *mut std::ffi::c_void));
+
+            // NSWorkspace notifications come from the workspace notification center,
+            // not NSApplication's, and have no `object` to filter on.
+            let workspace_class = Class::get("NSWorkspace").unwrap();
             #[allow(unexpected_cfgs)]
-            let notification_name: *mut Object = {
+            let workspace: *mut Object = msg_send![workspace_class, sharedWorkspace];
+            #[allow(unexpected_cfgs)]
+            let workspace_notification_center: *mut Object = msg_send![workspace, notificationCenter];
+
+            #[allow(unexpected_cfgs)]
+            let will_sleep_name: *mut Object = {
+                let nsstring_class = Class::get("NSString").unwrap();
+                let cstr = std::ffi::CString::new("NSWorkspaceWillSleepNotification").unwrap();
+                msg_send![nsstring_class, stringWithUTF8String: cstr.as_ptr()]
+            };
+            #[allow(unexpected_cfgs)]
+            let did_wake_name: *mut Object = {
                 let nsstring_class = Class::get("NSString").unwrap();
-                let cstr =
-                    std::ffi::CString::new("NSApplicationWillTerminateNotification").unwrap();
+                let cstr = std::ffi::CString::new("NSWorkspaceDidWakeNotification").unwrap();
                 msg_send![nsstring_class, stringWithUTF8String: cstr.as_ptr()]
             };
-            // Register for the applicationWillTerminate notification
+
+            #[allow(unexpected_cfgs)]
+            let _: () = msg_send![
+                workspace_notification_center,
+                addObserver:observer
+                selector:sel_will_sleep
+                name:will_sleep_name
+                object:std::ptr::null_mut::<Object>()
+            ];
             #[allow(unexpected_cfgs)]
             let _: () = msg_send![
-                notification_center,
+                workspace_notification_center,
                 addObserver:observer
-                selector:sel_app_will_terminate
-                name:notification_name
-                object:app
+                selector:sel_did_wake
+                name:did_wake_name
+                object:std::ptr::null_mut::<Object>()
+            ];
+            log::debug!("NSWorkspace sleep/wake observers registered successfully");
+        }
+    });
+}
+
+// Four-char codes (`FourCharCode`/`AEKeyword`) are just big-endian u32s of
+// their ASCII bytes, e.g. `keyDirectObject` is `'----'`.
+fn four_char_code(code: &[u8; 4]) -> u32 {
+    u32::from_be_bytes(*code)
+}
+
+// Install an Apple Event handler for `kInternetEventClass`/`kAEGetURL` (both
+// the four-char code `'GURL'`) so `open`-ing an `openbb://` URL, or clicking
+// one in a browser, routes into the running app instead of being ignored.
+pub fn register_url_event_handler(app_handle: AppHandle) {
+    URL_INIT.call_once(|| {
+        store_app_handle(app_handle);
+
+        unsafe {
+            // Import objc macros
+            use objc::runtime::{Class, Object, Sel};
+            use objc::{msg_send, sel, sel_impl};
+
+            // Define our Objective-C class
+            let superclass = Class::get("NSObject").unwrap();
+            let mut decl =
+                objc::declare::ClassDecl::new("OBBAppURLEventObserver", superclass).unwrap();
+
+            // Implement the handler for the GetURL Apple Event
+            extern "C" fn handle_get_url_event(
+                _this: &Object,
+                _cmd: Sel,
+                event: *mut Object,
+                _reply_event: *mut Object,
+            ) {
+                use objc::runtime::Object as ObjcObject;
+                use objc::{msg_send, sel, sel_impl};
+
+                unsafe {
+                    let key_direct_object = four_char_code(b"----");
+                    #[allow(unexpected_cfgs)]
+                    let direct_object: *mut ObjcObject =
+                        msg_send![event, paramDescriptorForKeyword: key_direct_object];
+                    if direct_object.is_null() {
+                        log::warn!("GetURL Apple Event had no direct object descriptor");
+                        return;
+                    }
+
+                    #[allow(unexpected_cfgs)]
+                    let url_nsstring: *mut ObjcObject = msg_send![direct_object, stringValue];
+                    if url_nsstring.is_null() {
+                        log::warn!("GetURL Apple Event direct object had no string value");
+                        return;
+                    }
+
+                    #[allow(unexpected_cfgs)]
+                    let utf8_ptr: *const std::os::raw::c_char =
+                        msg_send![url_nsstring, UTF8String];
+                    let url = std::ffi::CStr::from_ptr(utf8_ptr)
+                        .to_string_lossy()
+                        .into_owned();
+
+                    log::debug!("received deep link: {url}");
+                    emit_or_queue_deep_link(app_handle(), url);
+                }
+            }
+
+            // Add the method to our class
+            #[allow(unexpected_cfgs)]
+            let sel_handle_get_url_event = sel!(handleGetURLEvent:withReplyEvent:);
+            decl.add_method(
+                sel_handle_get_url_event,
+                handle_get_url_event as extern "C" fn(&Object, Sel, *mut Object, *mut Object),
+            );
+
+            // Register the class
+            let url_observer_class = decl.register();
+
+            // Create an instance
+            #[allow(unexpected_cfgs)]
+            let observer: *mut Object = msg_send![url_observer_class, new];
+
+            // Store the observer in our static to prevent it from being dropped
+            let _ = URL_OBSERVER.set(ObjcInstance(observer as *mut std::ffi::c_void));
+
+            // Get NSAppleEventManager's shared instance
+            let event_manager_class = Class::get("NSAppleEventManager").unwrap();
+            #[allow(unexpected_cfgs)]
+            let event_manager: *mut Object = msg_send![event_manager_class, sharedAppleEventManager];
+
+            // kInternetEventClass and kAEGetURLEvent are both `'GURL'`
+            let gurl_event_class = four_char_code(b"GURL");
+            let gurl_event_id = four_char_code(b"GURL");
+
+            #[allow(unexpected_cfgs)]
+            let _: () = msg_send![
+                event_manager,
+                setEventHandler:observer
+                andSelector:sel_handle_get_url_event
+                forEventClass:gurl_event_class
+                andEventID:gurl_event_id
             ];
-            log::debug!("applicationWillTerminate observer registered successfully");
+            log::debug!("Apple Event URL handler registered successfully");
         }
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn four_char_code_packs_ascii_bytes_big_endian() {
+        // kInternetEventClass and kAEGetURLEvent are both `'GURL'` — if this
+        // ever stopped matching Apple's four-char code encoding, the GetURL
+        // handler would silently register for the wrong event.
+        assert_eq!(four_char_code(b"GURL"), 0x4755524C);
+    }
+
+    #[test]
+    fn four_char_code_packs_key_direct_object() {
+        assert_eq!(four_char_code(b"----"), 0x2D2D2D2D);
+    }
+}
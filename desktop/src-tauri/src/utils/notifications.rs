@@ -0,0 +1,89 @@
+use notify_rust::{Notification, Timeout};
+
+/// How long a native notification should stay on screen before it auto-dismisses.
+///
+/// Mirrors the `sound_name`/timeout split notify-rust exposes, kept as its own
+/// enum so the Tauri command boundary stays serde-friendly instead of leaking
+/// notify-rust's `Timeout` type to the frontend.
+#[derive(Debug, Clone, Copy, serde::Deserialize)]
+#[serde(tag = "type", content = "value")]
+pub enum NotificationTimeout {
+    Never,
+    Milliseconds(u32),
+}
+
+impl From<NotificationTimeout> for Timeout {
+    fn from(value: NotificationTimeout) -> Self {
+        match value {
+            NotificationTimeout::Never => Timeout::Never,
+            NotificationTimeout::Milliseconds(ms) => Timeout::Milliseconds(ms),
+        }
+    }
+}
+
+/// Post a native OS notification so backend/job events are visible even
+/// when the terminal window is unfocused or backgrounded.
+#[tauri::command]
+pub fn notify(
+    title: String,
+    body: String,
+    subtitle: Option<String>,
+    icon: Option<String>,
+    sound: Option<String>,
+    timeout: Option<NotificationTimeout>,
+) -> Result<(), String> {
+    let mut notification = Notification::new();
+    notification.summary(&title).body(&body);
+
+    if let Some(subtitle) = subtitle {
+        notification.subtitle(&subtitle);
+    }
+    if let Some(icon) = icon {
+        notification.icon(&icon);
+    }
+    if let Some(sound) = sound {
+        notification.sound_name(&sound);
+    }
+    if let Some(timeout) = timeout {
+        notification.timeout(Timeout::from(timeout));
+    }
+
+    notification
+        .show()
+        .map(|_| ())
+        .map_err(|err| format!("failed to show notification: {err}"))
+}
+
+/// Best-effort "shutting down" notice posted from the termination path.
+/// Failures are logged rather than propagated since this fires during
+/// shutdown, when there's no one left to hand an `Err` to.
+pub fn notify_shutdown() {
+    if let Err(err) = notify(
+        "OpenBB Terminal".to_string(),
+        "Shutting down...".to_string(),
+        None,
+        None,
+        None,
+        Some(NotificationTimeout::Milliseconds(3000)),
+    ) {
+        log::warn!("failed to post shutdown notification: {err}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn never_converts_to_never() {
+        assert!(matches!(Timeout::from(NotificationTimeout::Never), Timeout::Never));
+    }
+
+    #[test]
+    fn milliseconds_round_trips_the_value() {
+        assert!(matches!(
+            Timeout::from(NotificationTimeout::Milliseconds(3000)),
+            Timeout::Milliseconds(3000)
+        ));
+    }
+}
@@ -0,0 +1,71 @@
+use std::sync::{Mutex, OnceLock};
+
+/// PIDs of spawned OpenBB backend processes that should be paused across a
+/// sleep/wake cycle instead of left holding live websocket/data connections
+/// open for the OS to eventually tear down. Whatever spawns the backend is
+/// responsible for calling `register_backend_pid`/`unregister_backend_pid` as
+/// processes come and go; this module only knows how to signal whatever PIDs
+/// are currently registered.
+static BACKEND_PIDS: OnceLock<Mutex<Vec<u32>>> = OnceLock::new();
+
+fn backend_pids() -> &'static Mutex<Vec<u32>> {
+    BACKEND_PIDS.get_or_init(|| Mutex::new(Vec::new()))
+}
+
+/// Track a spawned backend process so it can be suspended/resumed across
+/// sleep/wake.
+pub fn register_backend_pid(pid: u32) {
+    backend_pids().lock().unwrap().push(pid);
+}
+
+/// Stop tracking a backend process, e.g. once it's exited.
+pub fn unregister_backend_pid(pid: u32) {
+    backend_pids().lock().unwrap().retain(|&tracked| tracked != pid);
+}
+
+#[cfg(unix)]
+fn send_signal(pid: u32, signal: libc::c_int) {
+    // SAFETY: `kill` with a tracked PID and a valid signal number is sound;
+    // a missing/already-exited process just yields ESRCH, which we log.
+    let result = unsafe { libc::kill(pid as libc::pid_t, signal) };
+    if result != 0 {
+        log::warn!(
+            "failed to send signal {signal} to backend process {pid}: {}",
+            std::io::Error::last_os_error()
+        );
+    }
+}
+
+/// Suspend every tracked backend process with `SIGSTOP` so it stops spinning
+/// on its event loop / holding sockets open while the machine is asleep.
+pub async fn suspend_all_processes(_app_handle: tauri::AppHandle) {
+    #[cfg(unix)]
+    for pid in backend_pids().lock().unwrap().iter() {
+        log::debug!("suspending backend process {pid} (SIGSTOP)");
+        send_signal(*pid, libc::SIGSTOP);
+    }
+}
+
+/// Resume every tracked backend process with `SIGCONT` after the machine
+/// wakes back up.
+pub async fn resume_all_processes(_app_handle: tauri::AppHandle) {
+    #[cfg(unix)]
+    for pid in backend_pids().lock().unwrap().iter() {
+        log::debug!("resuming backend process {pid} (SIGCONT)");
+        send_signal(*pid, libc::SIGCONT);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn register_and_unregister_round_trip() {
+        register_backend_pid(123456);
+        assert!(backend_pids().lock().unwrap().contains(&123456));
+
+        unregister_backend_pid(123456);
+        assert!(!backend_pids().lock().unwrap().contains(&123456));
+    }
+}